@@ -1,4 +1,6 @@
-use std::str;
+use std::convert::TryFrom;
+use std::iter::Peekable;
+use std::str::{self, Chars};
 
 use crate::{EncodingContext, SharedData, SimpleVariantType};
 use crate::{Variant, VariantError, VariantType, VariantTypeConstants};
@@ -92,6 +94,7 @@ impl SimpleVariantType for String {}
 pub struct ObjectPath(String);
 
 impl ObjectPath {
+    /// Create a new `ObjectPath` without checking that `path` is well-formed.
     pub fn new(path: &str) -> Self {
         Self(String::from(path))
     }
@@ -99,6 +102,53 @@ impl ObjectPath {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Check that `s` is a syntactically valid object path, per the D-Bus specification: it must
+    /// start with `/`, each `/`-separated element must be non-empty and contain only
+    /// `[A-Za-z0-9_]`, and only the root path `/` may end in `/`.
+    pub fn validate(s: &str) -> Result<(), VariantError> {
+        if !s.starts_with('/') {
+            return Err(VariantError::InvalidObjectPath);
+        }
+        if s == "/" {
+            return Ok(());
+        }
+        if s.ends_with('/') {
+            return Err(VariantError::InvalidObjectPath);
+        }
+
+        for element in s[1..].split('/') {
+            if element.is_empty()
+                || !element
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return Err(VariantError::InvalidObjectPath);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for ObjectPath {
+    type Error = VariantError;
+
+    fn try_from(path: &str) -> Result<Self, VariantError> {
+        Self::validate(path)?;
+
+        Ok(Self::new(path))
+    }
+}
+
+impl TryFrom<String> for ObjectPath {
+    type Error = VariantError;
+
+    fn try_from(path: String) -> Result<Self, VariantError> {
+        Self::validate(&path)?;
+
+        Ok(Self(path))
+    }
 }
 
 impl VariantTypeConstants for ObjectPath {
@@ -138,7 +188,10 @@ impl VariantType for ObjectPath {
         context: EncodingContext,
     ) -> Result<Self, VariantError> {
         Self::ensure_correct_signature(signature)?;
-        String::decode(data, String::SIGNATURE_STR, context).map(|s| Self(s))
+        let s = String::decode(data, String::SIGNATURE_STR, context)?;
+        Self::validate(&s)?;
+
+        Ok(Self(s))
     }
 
     fn is(variant: &Variant) -> bool {
@@ -175,6 +228,7 @@ impl SimpleVariantType for ObjectPath {}
 pub struct Signature(String);
 
 impl Signature {
+    /// Create a new `Signature` without checking that `signature` is well-formed.
     pub fn new(signature: &str) -> Self {
         Self(String::from(signature))
     }
@@ -182,6 +236,103 @@ impl Signature {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Check that `s` is a syntactically valid D-Bus signature: a sequence of well-formed
+    /// complete types, at most 255 bytes long, with balanced `()` structs and `a`/`{}` container
+    /// nesting (dict entries only directly inside an array, with exactly two contained types and
+    /// a basic-type key).
+    pub fn validate(s: &str) -> Result<(), VariantError> {
+        if s.len() > 255 {
+            return Err(VariantError::InvalidSignature);
+        }
+
+        let mut chars = s.chars().peekable();
+        while chars.peek().is_some() {
+            validate_complete_type(&mut chars)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for Signature {
+    type Error = VariantError;
+
+    fn try_from(signature: &str) -> Result<Self, VariantError> {
+        Self::validate(signature)?;
+
+        Ok(Self::new(signature))
+    }
+}
+
+impl TryFrom<String> for Signature {
+    type Error = VariantError;
+
+    fn try_from(signature: String) -> Result<Self, VariantError> {
+        Self::validate(&signature)?;
+
+        Ok(Self(signature))
+    }
+}
+
+fn is_basic_type_code(c: char) -> bool {
+    matches!(c, 'y' | 'b' | 'n' | 'q' | 'i' | 'u' | 'x' | 't' | 'd' | 's' | 'o' | 'g' | 'h')
+}
+
+/// Consume one complete type off `chars`, failing if it's malformed.
+fn validate_complete_type(chars: &mut Peekable<Chars>) -> Result<(), VariantError> {
+    match chars.next() {
+        Some(c) if is_basic_type_code(c) || c == 'v' => Ok(()),
+        Some('a') => match chars.peek() {
+            Some('{') => {
+                chars.next();
+                validate_dict_entry(chars)
+            }
+            Some(_) => validate_complete_type(chars),
+            None => Err(VariantError::InvalidSignature),
+        },
+        Some('(') => validate_struct(chars),
+        _ => Err(VariantError::InvalidSignature),
+    }
+}
+
+/// Consume the contained types of a `(...)` struct, up to and including the closing `)`.
+fn validate_struct(chars: &mut Peekable<Chars>) -> Result<(), VariantError> {
+    let mut has_field = false;
+    loop {
+        match chars.peek() {
+            Some(')') => {
+                chars.next();
+
+                return if has_field {
+                    Ok(())
+                } else {
+                    Err(VariantError::InvalidSignature)
+                };
+            }
+            Some(_) => {
+                validate_complete_type(chars)?;
+                has_field = true;
+            }
+            None => return Err(VariantError::InvalidSignature),
+        }
+    }
+}
+
+/// Consume a dict-entry's key and value types, up to and including the closing `}`, assuming the
+/// opening `a{` has already been consumed.
+fn validate_dict_entry(chars: &mut Peekable<Chars>) -> Result<(), VariantError> {
+    match chars.next() {
+        Some(c) if is_basic_type_code(c) => (),
+        _ => return Err(VariantError::InvalidSignature),
+    }
+
+    validate_complete_type(chars)?;
+
+    match chars.next() {
+        Some('}') => Ok(()),
+        _ => Err(VariantError::InvalidSignature),
+    }
 }
 
 impl VariantTypeConstants for Signature {
@@ -243,9 +394,10 @@ impl VariantType for Signature {
         data.apply(|bytes| {
             crate::ensure_sufficient_bytes(bytes, last_index)?;
 
-            str::from_utf8(&bytes[1..last_index])
-                .map(|s| Self::new(s))
-                .map_err(|_| VariantError::InvalidUtf8)
+            let s = str::from_utf8(&bytes[1..last_index]).map_err(|_| VariantError::InvalidUtf8)?;
+            Self::validate(s)?;
+
+            Ok(Self::new(s))
         })
     }
 
@@ -277,4 +429,56 @@ impl VariantType for Signature {
         Variant::Signature(self)
     }
 }
-impl SimpleVariantType for Signature {}
\ No newline at end of file
+impl SimpleVariantType for Signature {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_path_validate() {
+        assert!(ObjectPath::validate("/").is_ok());
+        assert!(ObjectPath::validate("/a_1/B2").is_ok());
+
+        assert!(ObjectPath::validate("//a").is_err());
+        assert!(ObjectPath::validate("/a/").is_err());
+        assert!(ObjectPath::validate("a/b").is_err());
+        assert!(ObjectPath::validate("/a/./b").is_err());
+    }
+
+    #[test]
+    fn object_path_try_from() {
+        assert!(ObjectPath::try_from("/org/foo").is_ok());
+        assert!(ObjectPath::try_from("/org/foo/").is_err());
+        assert!(ObjectPath::try_from(String::from("/org/foo/")).is_err());
+    }
+
+    #[test]
+    fn signature_validate() {
+        assert!(Signature::validate("").is_ok());
+        assert!(Signature::validate("as").is_ok());
+        assert!(Signature::validate("a{sv}").is_ok());
+
+        // An empty struct has no contained types, which the spec doesn't allow.
+        assert!(Signature::validate("()").is_err());
+        // A dict entry is only a complete type directly inside an array.
+        assert!(Signature::validate("{sv}").is_err());
+        // Unterminated dict entry.
+        assert!(Signature::validate("a{si").is_err());
+    }
+
+    #[test]
+    fn signature_validate_length_boundary() {
+        let at_limit: String = std::iter::repeat('y').take(255).collect();
+        assert!(Signature::validate(&at_limit).is_ok());
+
+        let over_limit: String = std::iter::repeat('y').take(256).collect();
+        assert!(Signature::validate(&over_limit).is_err());
+    }
+
+    #[test]
+    fn signature_try_from() {
+        assert!(Signature::try_from("a{sv}").is_ok());
+        assert!(Signature::try_from("{sv}").is_err());
+        assert!(Signature::try_from(String::from("as")).is_ok());
+    }
+}
@@ -5,8 +5,8 @@ use static_assertions::assert_impl_all;
 
 use crate::{
     names::{BusName, InterfaceName, MemberName, UniqueName},
-    zvariant::{ObjectPath, Str, Type},
-    Error, MatchRuleBuilder, MessageType, Result,
+    zvariant::{ObjectPath, Str, Structure, Type, Value},
+    Error, MatchRuleBuilder, Message, MessageType, Result,
 };
 
 /// A bus match rule for subscribing to specific messages.
@@ -119,6 +119,95 @@ impl<'m> MatchRule<'m> {
         self.arg0namespace.as_ref()
     }
 
+    /// Check if the given `msg` satisfies this rule.
+    ///
+    /// All the components that are set on `self` must match the corresponding fields of `msg`
+    /// for this to return `true`, mirroring the logical AND semantics the bus itself uses when
+    /// routing messages against an installed match rule. Components that are not set on `self`
+    /// are ignored.
+    pub fn matches(&self, msg: &Message) -> Result<bool> {
+        if let Some(msg_type) = self.msg_type {
+            if msg_type != msg.message_type() {
+                return Ok(false);
+            }
+        }
+
+        if let Some(sender) = &self.sender {
+            match msg.sender() {
+                Some(msg_sender) if msg_sender == sender => (),
+                _ => return Ok(false),
+            }
+        }
+
+        if let Some(interface) = &self.interface {
+            match msg.interface() {
+                Some(msg_interface) if &msg_interface == interface => (),
+                _ => return Ok(false),
+            }
+        }
+
+        if let Some(member) = &self.member {
+            match msg.member() {
+                Some(msg_member) if &msg_member == member => (),
+                _ => return Ok(false),
+            }
+        }
+
+        if let Some(destination) = &self.destination {
+            match msg.header()?.destination()? {
+                Some(msg_destination) if msg_destination == destination => (),
+                _ => return Ok(false),
+            }
+        }
+
+        if let Some(path_spec) = &self.path_spec {
+            match msg.path() {
+                Some(msg_path) if path_spec.matches(&msg_path) => (),
+                _ => return Ok(false),
+            }
+        }
+
+        if !self.args.is_empty() || !self.arg_paths.is_empty() || self.arg0namespace.is_some() {
+            // A body that doesn't even deserialize as a generic tuple (e.g. no body at all, or
+            // one whose wire bytes don't match its declared signature) can't possibly satisfy an
+            // argument matcher, so we treat it the same as a present-but-mismatching body: a
+            // non-match rather than a propagated error.
+            let body = match msg.body::<Structure<'_>>() {
+                Ok(body) => body,
+                Err(_) => return Ok(false),
+            };
+            let fields = body.fields();
+
+            for (i, arg) in self.args.iter().enumerate() {
+                match fields.get(i) {
+                    Some(Value::Str(value)) if value.as_str() == arg.as_str() => (),
+                    _ => return Ok(false),
+                }
+            }
+
+            for (i, arg_path) in self.arg_paths.iter().enumerate() {
+                let value = match fields.get(i) {
+                    Some(Value::ObjectPath(value)) => value.as_str(),
+                    Some(Value::Str(value)) => value.as_str(),
+                    _ => return Ok(false),
+                };
+                if !path_or_prefix_matches(value, arg_path.as_str()) {
+                    return Ok(false);
+                }
+            }
+
+            if let Some(arg0namespace) = &self.arg0namespace {
+                match fields.first() {
+                    Some(Value::Str(arg0))
+                        if namespace_matches(arg0.as_str(), arg0namespace.as_str()) => {}
+                    _ => return Ok(false),
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Creates an owned clone of `self`.
     pub fn to_owned(&self) -> MatchRule<'static> {
         MatchRule {
@@ -199,6 +288,23 @@ fn add_match_rule_string_component(rule: &mut String, key: &str, value: &str) {
     rule.push('\'');
 }
 
+/// `arg_path` matching, per the `arg_path` match rule convention: an exact match, or a
+/// prefix-of-path-components match when either side ends in `/`.
+fn path_or_prefix_matches(value: &str, arg_path: &str) -> bool {
+    if value == arg_path {
+        return true;
+    }
+
+    (arg_path.ends_with('/') && value.starts_with(arg_path))
+        || (value.ends_with('/') && arg_path.starts_with(value))
+}
+
+/// `arg0namespace` matching: `arg0` is either exactly the namespace, or starts with
+/// `namespace.`.
+fn namespace_matches(arg0: &str, namespace: &str) -> bool {
+    arg0 == namespace || arg0.starts_with(&format!("{namespace}."))
+}
+
 impl<'m> TryFrom<&'m str> for MatchRule<'m> {
     type Error = Error;
 
@@ -276,6 +382,21 @@ pub enum MatchRulePathSpec<'m> {
 assert_impl_all!(MatchRulePathSpec<'_>: Send, Sync, Unpin);
 
 impl<'m> MatchRulePathSpec<'m> {
+    /// Check if `path` satisfies this path specification.
+    fn matches(&self, path: &ObjectPath<'_>) -> bool {
+        match self {
+            MatchRulePathSpec::Path(p) => p == path,
+            MatchRulePathSpec::PathNamespace(ns) => {
+                let ns = ns.as_str();
+                let path = path.as_str();
+
+                path == ns
+                    || ns == "/"
+                    || (path.starts_with(ns) && path[ns.len()..].starts_with('/'))
+            }
+        }
+    }
+
     /// Creates an owned clone of `self`.
     fn to_owned(&self) -> MatchRulePathSpec<'static> {
         match self {
@@ -367,3 +488,144 @@ impl PartialEq<MatchRule<'_>> for OwnedMatchRule {
         self.0 == *other
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageBuilder;
+
+    fn signal(path: &str, interface: &str, member: &str, body: &impl serde::Serialize) -> Message {
+        MessageBuilder::signal(path, interface, member)
+            .unwrap()
+            .build(body)
+            .unwrap()
+    }
+
+    #[test]
+    fn msg_type_matches() {
+        let rule = MatchRule::builder().msg_type(MessageType::Signal).build();
+        let msg = signal("/", "a.b", "M", &());
+
+        assert!(rule.matches(&msg).unwrap());
+
+        let rule = MatchRule::builder()
+            .msg_type(MessageType::MethodCall)
+            .build();
+
+        assert!(!rule.matches(&msg).unwrap());
+    }
+
+    #[test]
+    fn path_exact_matches() {
+        let rule = MatchRule::builder().path("/org/foo").unwrap().build();
+
+        assert!(rule
+            .matches(&signal("/org/foo", "a.b", "M", &()))
+            .unwrap());
+        assert!(!rule
+            .matches(&signal("/org/foo/bar", "a.b", "M", &()))
+            .unwrap());
+    }
+
+    #[test]
+    fn path_namespace_root_matches_everything() {
+        let rule = MatchRule::builder().path_namespace("/").unwrap().build();
+
+        assert!(rule.matches(&signal("/", "a.b", "M", &())).unwrap());
+        assert!(rule
+            .matches(&signal("/org/foo/bar", "a.b", "M", &()))
+            .unwrap());
+    }
+
+    #[test]
+    fn path_namespace_is_a_proper_prefix() {
+        let rule = MatchRule::builder()
+            .path_namespace("/org/foo")
+            .unwrap()
+            .build();
+
+        assert!(rule
+            .matches(&signal("/org/foo", "a.b", "M", &()))
+            .unwrap());
+        assert!(rule
+            .matches(&signal("/org/foo/bar", "a.b", "M", &()))
+            .unwrap());
+        // `/org/foobar` has `/org/foo` as a string prefix, but not as a path-component prefix.
+        assert!(!rule
+            .matches(&signal("/org/foobar", "a.b", "M", &()))
+            .unwrap());
+    }
+
+    #[test]
+    fn args_must_be_string_equal() {
+        let rule = MatchRule::builder().add_arg("hello").unwrap().build();
+
+        assert!(rule
+            .matches(&signal("/", "a.b", "M", &("hello",)))
+            .unwrap());
+        assert!(!rule
+            .matches(&signal("/", "a.b", "M", &("goodbye",)))
+            .unwrap());
+    }
+
+    #[test]
+    fn missing_argument_is_non_match_not_error() {
+        let rule = MatchRule::builder().add_arg("hello").unwrap().build();
+
+        // No body at all, so argument 0 can't be compared: this must be a clean non-match, not
+        // a propagated deserialization error.
+        assert!(!rule.matches(&signal("/", "a.b", "M", &())).unwrap());
+    }
+
+    #[test]
+    fn arg_path_prefix_matches_in_both_directions() {
+        let rule = MatchRule::builder()
+            .add_arg_path("/org/foo/")
+            .unwrap()
+            .build();
+
+        // Rule value ends in `/`: matches a deeper path under it.
+        assert!(rule
+            .matches(&signal("/", "a.b", "M", &("/org/foo/bar",)))
+            .unwrap());
+
+        let rule = MatchRule::builder().add_arg_path("/org/foo/bar").unwrap().build();
+
+        // Argument value ends in `/`: matches a shallower namespace that contains it.
+        assert!(rule
+            .matches(&signal("/", "a.b", "M", &("/org/foo/",)))
+            .unwrap());
+
+        assert!(!rule
+            .matches(&signal("/", "a.b", "M", &("/org/bar",)))
+            .unwrap());
+    }
+
+    #[test]
+    fn arg0namespace_matches_prefix_component() {
+        let rule = MatchRule::builder()
+            .arg0namespace("org.freedesktop")
+            .unwrap()
+            .build();
+
+        assert!(rule
+            .matches(&signal("/", "a.b", "M", &("org.freedesktop.DBus",)))
+            .unwrap());
+        assert!(rule
+            .matches(&signal("/", "a.b", "M", &("org.freedesktop",)))
+            .unwrap());
+        assert!(!rule
+            .matches(&signal("/", "a.b", "M", &("org.freedesktopish",)))
+            .unwrap());
+    }
+
+    #[test]
+    fn non_string_argument_is_non_match() {
+        // Argument 0 is a `u32`, not a string, so it can't string-equal `hello`; this must
+        // surface as a non-match, the same as a missing argument, rather than as an error.
+        let rule = MatchRule::builder().add_arg("hello").unwrap().build();
+        let msg = signal("/", "a.b", "M", &42u32);
+
+        assert!(!rule.matches(&msg).unwrap());
+    }
+}
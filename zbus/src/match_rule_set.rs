@@ -0,0 +1,182 @@
+use static_assertions::assert_impl_all;
+
+use crate::{MatchRule, Message, Result};
+
+/// A boolean combination of [`MatchRule`]s for client-side filtering.
+///
+/// The bus itself only understands OR semantics across the match rules a peer installs: every
+/// rule that's currently registered can independently cause a message to be delivered. There's no
+/// way to ask the bus for "messages matching A but not B". `MatchRuleSet` fills that gap by
+/// letting you build a predicate tree out of `Any` (union), `All` (intersection) and `Not`
+/// (negation) nodes over plain `MatchRule`s, and evaluate it locally with [`matches`] against
+/// messages you've already received.
+///
+/// Since the bus has no concept of `All` or `Not`, registering a `MatchRuleSet` with the bus
+/// requires lowering it to a set of plain match rules that ensures every message the tree could
+/// possibly accept actually arrives. See [`underlying_rules`] for the details of that lowering.
+///
+/// [`matches`]: MatchRuleSet::matches
+/// [`underlying_rules`]: MatchRuleSet::underlying_rules
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MatchRuleSet<'m> {
+    /// Matches if any of the contained sets match (union).
+    Any(Vec<MatchRuleSet<'m>>),
+    /// Matches if all of the contained sets match (intersection).
+    All(Vec<MatchRuleSet<'m>>),
+    /// Matches if the contained set does not match (negation).
+    Not(Box<MatchRuleSet<'m>>),
+    /// Matches if the leaf rule matches.
+    Rule(MatchRule<'m>),
+}
+
+assert_impl_all!(MatchRuleSet<'_>: Send, Sync, Unpin);
+
+impl<'m> MatchRuleSet<'m> {
+    /// Create a set that matches if any of `sets` match.
+    pub fn any(sets: impl IntoIterator<Item = impl Into<MatchRuleSet<'m>>>) -> Self {
+        Self::Any(sets.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a set that matches if all of `sets` match.
+    pub fn all(sets: impl IntoIterator<Item = impl Into<MatchRuleSet<'m>>>) -> Self {
+        Self::All(sets.into_iter().map(Into::into).collect())
+    }
+
+    /// Negate this set.
+    pub fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+
+    /// Check if the given `msg` satisfies this set.
+    pub fn matches(&self, msg: &Message) -> Result<bool> {
+        match self {
+            Self::Any(sets) => {
+                for set in sets {
+                    if set.matches(msg)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+            Self::All(sets) => {
+                for set in sets {
+                    if !set.matches(msg)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+            Self::Not(set) => set.matches(msg).map(|matched| !matched),
+            Self::Rule(rule) => rule.matches(msg),
+        }
+    }
+
+    /// The plain match rules that must be registered with the bus for this set to work.
+    ///
+    /// The bus only ever ORs installed match rules together, so this flattens `Any`/`All` nodes
+    /// into the union of their leaf rules. A `Not` node (and an empty `All`, which [`matches`]
+    /// treats as vacuously true) can't be expressed as "deliver everything except these", so it's
+    /// conservatively expanded to a catch-all rule with no fields set, ensuring the bus over-
+    /// rather than under-delivers; [`matches`] then does the exact filtering once the message has
+    /// arrived.
+    ///
+    /// The returned rules are not deduplicated, and the other rules are still returned alongside
+    /// a catch-all rule.
+    ///
+    /// [`matches`]: MatchRuleSet::matches
+    pub fn underlying_rules(&self) -> Vec<MatchRule<'m>> {
+        let mut rules = vec![];
+        self.collect_underlying_rules(&mut rules);
+
+        rules
+    }
+
+    fn collect_underlying_rules(&self, rules: &mut Vec<MatchRule<'m>>) {
+        match self {
+            Self::Any(sets) => {
+                for set in sets {
+                    set.collect_underlying_rules(rules);
+                }
+            }
+            Self::All(sets) if sets.is_empty() => rules.push(MatchRule::builder().build()),
+            Self::All(sets) => {
+                for set in sets {
+                    set.collect_underlying_rules(rules);
+                }
+            }
+            Self::Not(_) => rules.push(MatchRule::builder().build()),
+            Self::Rule(rule) => rules.push(rule.clone()),
+        }
+    }
+}
+
+impl<'m> From<MatchRule<'m>> for MatchRuleSet<'m> {
+    fn from(rule: MatchRule<'m>) -> Self {
+        Self::Rule(rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageBuilder;
+
+    fn signal(interface: &str) -> Message {
+        MessageBuilder::signal("/", interface, "M")
+            .unwrap()
+            .build(&())
+            .unwrap()
+    }
+
+    fn rule(interface: &str) -> MatchRule<'static> {
+        MatchRule::builder()
+            .interface(interface)
+            .unwrap()
+            .build()
+            .into_owned()
+    }
+
+    #[test]
+    fn not_lowers_to_a_catch_all_rule() {
+        let set = MatchRuleSet::from(rule("a.b")).not();
+
+        // The bus can't express "not this", so it must get a wildcard rule: anything less would
+        // mean messages the set *should* accept (anything but `a.b`) never arrive at all.
+        assert_eq!(set.underlying_rules(), vec![MatchRule::builder().build()]);
+
+        assert!(!set.matches(&signal("a.b")).unwrap());
+        assert!(set.matches(&signal("a.c")).unwrap());
+    }
+
+    #[test]
+    fn empty_all_lowers_to_a_catch_all_rule() {
+        let set: MatchRuleSet<'static> = MatchRuleSet::All(vec![]);
+
+        // `matches` treats an empty `All` as vacuously true, so the bus must deliver everything
+        // for that to actually be observable, not zero rules.
+        assert_eq!(set.underlying_rules(), vec![MatchRule::builder().build()]);
+        assert!(set.matches(&signal("a.b")).unwrap());
+    }
+
+    #[test]
+    fn nested_any_all_not_flattens_and_filters_correctly() {
+        let all = MatchRuleSet::all([rule("a.b"), rule("a.b")]);
+        let not = MatchRuleSet::from(rule("a.c")).not();
+        let set = MatchRuleSet::any([all, not]);
+
+        let mut underlying = set.underlying_rules();
+        underlying.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+        let mut expected = vec![rule("a.b"), rule("a.b"), MatchRule::builder().build()];
+        expected.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+        assert_eq!(underlying, expected);
+
+        // Matches via the `All(a.b, a.b)` branch.
+        assert!(set.matches(&signal("a.b")).unwrap());
+        // Doesn't match `All(a.b, a.b)`, but does match `Not(a.c)`.
+        assert!(set.matches(&signal("a.d")).unwrap());
+        // Matches neither branch: `All` fails and `a.c` satisfies the negated rule.
+        assert!(!set.matches(&signal("a.c")).unwrap());
+    }
+}